@@ -0,0 +1,108 @@
+//! A chunk of bytecode: a flat byte stream, its constant pool, and a
+//! per-byte line table used to point runtime errors back at the source.
+
+use crate::interpreter::Value;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+pub enum OpCode {
+	Constant,
+	Add,
+	Subtract,
+	Multiply,
+	Divide,
+	Negate,
+	Return,
+	True,
+	False,
+	Nil,
+	Equal,
+	Greater,
+	Less,
+	Print,
+	Pop,
+	DefineGlobal,
+	GetGlobal,
+	SetGlobal,
+	Jump,
+	JumpIfFalse,
+	Loop,
+	Assert,
+}
+
+impl TryFrom<u8,> for OpCode {
+	type Error = ();
+
+	fn try_from(byte: u8,) -> Result<Self, (),> {
+		use OpCode::*;
+		Ok(match byte {
+			0 => Constant,
+			1 => Add,
+			2 => Subtract,
+			3 => Multiply,
+			4 => Divide,
+			5 => Negate,
+			6 => Return,
+			7 => True,
+			8 => False,
+			9 => Nil,
+			10 => Equal,
+			11 => Greater,
+			12 => Less,
+			13 => Print,
+			14 => Pop,
+			15 => DefineGlobal,
+			16 => GetGlobal,
+			17 => SetGlobal,
+			18 => Jump,
+			19 => JumpIfFalse,
+			20 => Loop,
+			21 => Assert,
+			_ => return Err((),),
+		},)
+	}
+}
+
+/// A single compiled unit: opcodes and operand bytes, the literal values
+/// they reference, and the source line each byte came from.
+#[derive(Debug, Clone, Default,)]
+pub struct Chunk {
+	code:      Vec<u8,>,
+	lines:     Vec<usize,>,
+	constants: Vec<Value,>,
+}
+
+impl Chunk {
+	pub fn new() -> Self { Self::default() }
+
+	pub fn write_op(&mut self, op: OpCode, line: usize,) -> usize { self.write_byte(op as u8, line,) }
+
+	pub fn write_byte(&mut self, byte: u8, line: usize,) -> usize {
+		self.code.push(byte,);
+		self.lines.push(line,);
+		self.code.len() - 1
+	}
+
+	/// Adds a value to the constant pool and returns its index, or `None` if
+	/// the pool is already full: the index is stored in a single byte, so a
+	/// chunk can only ever hold up to `u8::MAX + 1` constants.
+	pub fn add_constant(&mut self, value: Value,) -> Option<u8,> {
+		if self.constants.len() > u8::MAX as usize {
+			return None;
+		}
+		self.constants.push(value,);
+		Some((self.constants.len() - 1) as u8,)
+	}
+
+	pub fn patch_byte(&mut self, offset: usize, byte: u8,) { self.code[offset] = byte; }
+
+	pub fn read_byte(&self, offset: usize,) -> u8 { self.code[offset] }
+
+	pub fn read_constant(&self, index: u8,) -> Value { self.constants[index as usize].clone() }
+
+	pub fn line_at(&self, offset: usize,) -> usize { self.lines.get(offset,).copied().unwrap_or(0,) }
+
+	pub fn len(&self,) -> usize { self.code.len() }
+
+	pub fn is_empty(&self,) -> bool { self.code.is_empty() }
+}
@@ -0,0 +1,626 @@
+//! A single-pass compiler: it walks the token stream once, using Pratt
+//! parsing for expressions, and emits bytecode directly into a [`Chunk`]
+//! instead of building an intermediate AST.
+
+use std::collections::HashMap;
+
+use crate::bytecode::chunk::Chunk;
+use crate::bytecode::chunk::OpCode;
+use crate::err_report;
+use crate::err_report::ErrorKind;
+use crate::err_report::ErrorSink;
+use crate::err_report::InterpreterError;
+use crate::interpreter::Value;
+use crate::token::Token;
+use crate::token::TokenType;
+use crate::token::TokenType::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,)]
+enum Precedence {
+	None,
+	Assignment,
+	Or,
+	And,
+	Equality,
+	Comparison,
+	Term,
+	Factor,
+	Unary,
+	Primary,
+}
+
+impl Precedence {
+	fn next(self,) -> Self {
+		use Precedence::*;
+		match self {
+			None => Assignment,
+			Assignment => Or,
+			Or => And,
+			And => Equality,
+			Equality => Comparison,
+			Comparison => Term,
+			Term => Factor,
+			Factor => Unary,
+			Unary => Primary,
+			Primary => Primary,
+		}
+	}
+}
+
+fn precedence_of(tt: &TokenType,) -> Precedence {
+	match tt {
+		Or => Precedence::Or,
+		And => Precedence::And,
+		BangEqual | EqualEqual => Precedence::Equality,
+		Greater | GreaterEqual | Less | LessEqual => Precedence::Comparison,
+		Plus | Minus => Precedence::Term,
+		Star | Slash => Precedence::Factor,
+		_ => Precedence::None,
+	}
+}
+
+type CompileResult<T,> = Result<T, InterpreterError,>;
+
+/// Compiles a token stream straight into a [`Chunk`], mirroring
+/// [`crate::parser::Parser`]'s cursor-based recursive descent but emitting
+/// bytes instead of building an AST. Only global variables are supported:
+/// blocks introduce no new scope, since the requested opcode set has no
+/// locals support.
+///
+/// Unlike `Parser`, which indexes a fully materialized `Vec<Token>`, this
+/// pulls tokens one at a time from any `Iterator<Item = Token>` (in
+/// particular [`crate::scanner::Scanner`] itself), buffering only the
+/// current and previous token the way `peek`/`previous` need.
+pub struct Compiler<I: Iterator<Item = Token,>,> {
+	tokens:       I,
+	current:      Token,
+	previous:     Option<Token,>,
+	chunk:        Chunk,
+	src:          String,
+	sink:         ErrorSink,
+	global_names: HashMap<String, u8,>,
+}
+
+impl<I: Iterator<Item = Token,>,> Compiler<I,> {
+	pub fn new(tokens: impl IntoIterator<Item = Token, IntoIter = I,>, src: String, sink: ErrorSink,) -> Self {
+		let mut tokens = tokens.into_iter();
+		let current = tokens.next().expect("token stream must yield at least an EOF token",);
+		Self { tokens, current, previous: None, chunk: Chunk::new(), src, sink, global_names: HashMap::new(), }
+	}
+
+	/// Compiles the whole token stream into a finished chunk, recovering
+	/// from syntax errors at statement boundaries just like the AST parser.
+	pub fn compile(mut self,) -> Chunk {
+		while !self.at_end() {
+			self.declaration();
+		}
+		let line = self.previous.as_ref().map_or(self.current.line(), |t| t.line(),);
+		self.chunk.write_op(OpCode::Return, line,);
+		self.chunk
+	}
+
+	fn declaration(&mut self,) {
+		let result =
+			if self.match_tokens(&[Var],) { self.var_declaration() } else { self.statement() };
+		if result.is_err() {
+			self.synchronize();
+		}
+	}
+
+	fn var_declaration(&mut self,) -> CompileResult<(),> {
+		let name = self.consume(Identifier, "variable name".to_string(),)?;
+		let constant = self.identifier_constant(name.lexeme(),)?;
+
+		if self.match_tokens(&[Equal],) {
+			self.expression()?;
+		} else {
+			self.chunk.write_op(OpCode::Nil, name.line(),);
+		}
+		self.consume(Semicolon, "';' after variable declaration".to_string(),)?;
+		self.chunk.write_op(OpCode::DefineGlobal, name.line(),);
+		self.chunk.write_byte(constant, name.line(),);
+		Ok((),)
+	}
+
+	fn statement(&mut self,) -> CompileResult<(),> {
+		if self.match_tokens(&[Print],) {
+			self.print_statement()
+		} else if self.match_tokens(&[Assert],) {
+			self.assert_statement()
+		} else if self.match_tokens(&[If],) {
+			self.if_statement()
+		} else if self.match_tokens(&[While],) {
+			self.while_statement()
+		} else if self.match_tokens(&[For],) {
+			self.for_statement()
+		} else if self.match_tokens(&[LeftBrace],) {
+			self.block()
+		} else {
+			self.expression_statement()
+		}
+	}
+
+	fn print_statement(&mut self,) -> CompileResult<(),> {
+		self.expression()?;
+		let line = self.previous().line();
+		self.consume(Semicolon, "';' after value".to_string(),)?;
+		self.chunk.write_op(OpCode::Print, line,);
+		Ok((),)
+	}
+
+	fn assert_statement(&mut self,) -> CompileResult<(),> {
+		self.expression()?;
+		let line = self.previous().line();
+		self.consume(Semicolon, "';' after value".to_string(),)?;
+		self.chunk.write_op(OpCode::Assert, line,);
+		Ok((),)
+	}
+
+	fn expression_statement(&mut self,) -> CompileResult<(),> {
+		self.expression()?;
+		let line = self.previous().line();
+		self.consume(Semicolon, "';' after expression".to_string(),)?;
+		self.chunk.write_op(OpCode::Pop, line,);
+		Ok((),)
+	}
+
+	/// Blocks have no dedicated scope here: this backend only knows globals,
+	/// so nested declarations simply (re)define the enclosing global.
+	fn block(&mut self,) -> CompileResult<(),> {
+		while !self.check(RightBrace,) && !self.at_end() {
+			self.declaration();
+		}
+		self.consume(RightBrace, "'}' after block".to_string(),)?;
+		Ok((),)
+	}
+
+	fn if_statement(&mut self,) -> CompileResult<(),> {
+		self.consume(LeftParen, "'(' after 'if'".to_string(),)?;
+		self.expression()?;
+		let line = self.previous().line();
+		self.consume(RightParen, "')' after if condition".to_string(),)?;
+
+		let then_jump = self.emit_jump(OpCode::JumpIfFalse, line,);
+		self.chunk.write_op(OpCode::Pop, line,);
+		self.statement()?;
+
+		let else_jump = self.emit_jump(OpCode::Jump, line,);
+		self.patch_jump(then_jump,);
+		self.chunk.write_op(OpCode::Pop, line,);
+
+		if self.match_tokens(&[Else],) {
+			self.statement()?;
+		}
+		self.patch_jump(else_jump,);
+		Ok((),)
+	}
+
+	fn while_statement(&mut self,) -> CompileResult<(),> {
+		let loop_start = self.chunk.len();
+		self.consume(LeftParen, "'(' after 'while'".to_string(),)?;
+		self.expression()?;
+		let line = self.previous().line();
+		self.consume(RightParen, "')' after while condition".to_string(),)?;
+
+		let exit_jump = self.emit_jump(OpCode::JumpIfFalse, line,);
+		self.chunk.write_op(OpCode::Pop, line,);
+		self.statement()?;
+		self.emit_loop(loop_start, line,);
+
+		self.patch_jump(exit_jump,);
+		self.chunk.write_op(OpCode::Pop, line,);
+		Ok((),)
+	}
+
+	/// Desugars `for (init; cond; incr) body` into the equivalent `while`
+	/// loop at the bytecode level, splicing the increment in just before
+	/// the jump back to the condition, matching the book's approach.
+	fn for_statement(&mut self,) -> CompileResult<(),> {
+		self.consume(LeftParen, "'(' after 'for'".to_string(),)?;
+
+		if self.match_tokens(&[Semicolon],) {
+			// no initializer
+		} else if self.match_tokens(&[Var],) {
+			self.var_declaration()?;
+		} else {
+			self.expression_statement()?;
+		}
+
+		let mut loop_start = self.chunk.len();
+		let mut exit_jump = None;
+		if !self.check(Semicolon,) {
+			self.expression()?;
+			let line = self.previous().line();
+			exit_jump = Some(self.emit_jump(OpCode::JumpIfFalse, line,),);
+			self.chunk.write_op(OpCode::Pop, line,);
+		}
+		let line = self.previous().line();
+		self.consume(Semicolon, "';' after loop condition".to_string(),)?;
+
+		if !self.check(RightParen,) {
+			let body_jump = self.emit_jump(OpCode::Jump, line,);
+			let increment_start = self.chunk.len();
+			self.expression()?;
+			let line = self.previous().line();
+			self.chunk.write_op(OpCode::Pop, line,);
+			self.consume(RightParen, "')' after for clauses".to_string(),)?;
+
+			self.emit_loop(loop_start, line,);
+			loop_start = increment_start;
+			self.patch_jump(body_jump,);
+		}
+
+		self.statement()?;
+		self.emit_loop(loop_start, line,);
+
+		if let Some(exit_jump,) = exit_jump {
+			self.patch_jump(exit_jump,);
+			self.chunk.write_op(OpCode::Pop, line,);
+		}
+		Ok((),)
+	}
+
+	/// Adds a value to the chunk's constant pool, reporting a compile error
+	/// instead of silently wrapping once the pool's single-byte index is full.
+	fn add_constant(&mut self, value: Value,) -> CompileResult<u8,> {
+		match self.chunk.add_constant(value,) {
+			Some(index,) => Ok(index,),
+			None => Err(self.error(self.previous(), ErrorKind::TooManyConstants,),),
+		}
+	}
+
+	/// Interns a global variable's name in the constant pool, reusing the
+	/// existing index for a name already seen instead of pushing a fresh
+	/// constant for every syntactic reference to it.
+	fn identifier_constant(&mut self, name: &str,) -> CompileResult<u8,> {
+		if let Some(&index,) = self.global_names.get(name,) {
+			return Ok(index,);
+		}
+		let index = self.add_constant(Value::Str(name.to_string(),),)?;
+		self.global_names.insert(name.to_string(), index,);
+		Ok(index,)
+	}
+
+	fn expression(&mut self,) -> CompileResult<(),> { self.parse_precedence(Precedence::Assignment,) }
+
+	fn parse_precedence(&mut self, prec: Precedence,) -> CompileResult<(),> {
+		self.advance();
+		let can_assign = prec <= Precedence::Assignment;
+		if !self.prefix(can_assign,)? {
+			let found = self.previous().token_type().clone();
+			let err = ErrorKind::ExpectedToken("expression".to_string(), found,);
+			return Err(self.error(self.previous(), err,),);
+		}
+
+		while prec <= precedence_of(self.peek().token_type(),) {
+			self.advance();
+			self.infix(can_assign,)?;
+		}
+
+		if can_assign && self.match_tokens(&[Equal],) {
+			self.error(self.previous(), ErrorKind::InvalidAssignmentTarget,);
+		}
+		Ok((),)
+	}
+
+	/// Dispatches on the just-consumed token as a prefix (leading) position:
+	/// a literal, a unary operator, a grouping, or a variable reference.
+	/// Returns `false` if the token can't start an expression at all.
+	fn prefix(&mut self, can_assign: bool,) -> CompileResult<bool,> {
+		match self.previous().token_type().clone() {
+			Number => self.number()?,
+			Str => self.string()?,
+			Char => self.char_lit()?,
+			True | False | Nil => self.literal(),
+			LeftParen => self.grouping()?,
+			Bang | Minus => self.unary()?,
+			Identifier => self.variable(can_assign,)?,
+			_ => return Ok(false,),
+		}
+		Ok(true,)
+	}
+
+	/// Dispatches on the just-consumed token as an infix (operator)
+	/// position, following a fully-parsed left-hand operand.
+	fn infix(&mut self, can_assign: bool,) -> CompileResult<(),> {
+		match self.previous().token_type().clone() {
+			Plus | Minus | Star | Slash | BangEqual | EqualEqual | Greater | GreaterEqual | Less
+			| LessEqual => self.binary()?,
+			And => self.and()?,
+			Or => self.or()?,
+			_ => {
+				let _ = can_assign;
+			},
+		}
+		Ok((),)
+	}
+
+	fn number(&mut self,) -> CompileResult<(),> {
+		let line = self.previous().line();
+		let n = self.previous().lexeme().parse().unwrap_or(0.0,);
+		let constant = self.add_constant(Value::Number(n,),)?;
+		self.chunk.write_op(OpCode::Constant, line,);
+		self.chunk.write_byte(constant, line,);
+		Ok((),)
+	}
+
+	fn string(&mut self,) -> CompileResult<(),> {
+		let line = self.previous().line();
+		let lexeme = self.previous().lexeme();
+		let s = lexeme[1..lexeme.len() - 1].to_string();
+		let constant = self.add_constant(Value::Str(s,),)?;
+		self.chunk.write_op(OpCode::Constant, line,);
+		self.chunk.write_byte(constant, line,);
+		Ok((),)
+	}
+
+	fn char_lit(&mut self,) -> CompileResult<(),> {
+		let line = self.previous().line();
+		let lexeme = self.previous().lexeme();
+		let c = lexeme.chars().nth(1,).unwrap_or('\0',);
+		let constant = self.add_constant(Value::Char(c,),)?;
+		self.chunk.write_op(OpCode::Constant, line,);
+		self.chunk.write_byte(constant, line,);
+		Ok((),)
+	}
+
+	fn literal(&mut self,) {
+		let line = self.previous().line();
+		match self.previous().token_type() {
+			True => self.chunk.write_op(OpCode::True, line,),
+			False => self.chunk.write_op(OpCode::False, line,),
+			Nil => self.chunk.write_op(OpCode::Nil, line,),
+			_ => unreachable!(),
+		};
+	}
+
+	fn grouping(&mut self,) -> CompileResult<(),> {
+		self.expression()?;
+		self.consume(RightParen, "')' after expression".to_string(),)?;
+		Ok((),)
+	}
+
+	fn unary(&mut self,) -> CompileResult<(),> {
+		let op = self.previous().token_type().clone();
+		let line = self.previous().line();
+		self.parse_precedence(Precedence::Unary,)?;
+		match op {
+			Minus => self.chunk.write_op(OpCode::Negate, line,),
+			// `!x` has no dedicated opcode: it's compiled as `x == false`.
+			Bang => {
+				self.chunk.write_op(OpCode::False, line,);
+				self.chunk.write_op(OpCode::Equal, line,)
+			},
+			_ => unreachable!(),
+		};
+		Ok((),)
+	}
+
+	fn binary(&mut self,) -> CompileResult<(),> {
+		let op = self.previous().token_type().clone();
+		let line = self.previous().line();
+		let prec = precedence_of(&op,);
+		self.parse_precedence(prec.next(),)?;
+
+		match op {
+			Plus => self.chunk.write_op(OpCode::Add, line,),
+			Minus => self.chunk.write_op(OpCode::Subtract, line,),
+			Star => self.chunk.write_op(OpCode::Multiply, line,),
+			Slash => self.chunk.write_op(OpCode::Divide, line,),
+			EqualEqual => self.chunk.write_op(OpCode::Equal, line,),
+			Greater => self.chunk.write_op(OpCode::Greater, line,),
+			Less => self.chunk.write_op(OpCode::Less, line,),
+			// The remaining comparisons have no dedicated opcode either, so
+			// they're built from the three above plus a `== false` negation.
+			BangEqual => {
+				self.chunk.write_op(OpCode::Equal, line,);
+				self.chunk.write_op(OpCode::False, line,);
+				self.chunk.write_op(OpCode::Equal, line,)
+			},
+			GreaterEqual => {
+				self.chunk.write_op(OpCode::Less, line,);
+				self.chunk.write_op(OpCode::False, line,);
+				self.chunk.write_op(OpCode::Equal, line,)
+			},
+			LessEqual => {
+				self.chunk.write_op(OpCode::Greater, line,);
+				self.chunk.write_op(OpCode::False, line,);
+				self.chunk.write_op(OpCode::Equal, line,)
+			},
+			_ => unreachable!(),
+		};
+		Ok((),)
+	}
+
+	fn and(&mut self,) -> CompileResult<(),> {
+		let line = self.previous().line();
+		let end_jump = self.emit_jump(OpCode::JumpIfFalse, line,);
+		self.chunk.write_op(OpCode::Pop, line,);
+		self.parse_precedence(Precedence::And,)?;
+		self.patch_jump(end_jump,);
+		Ok((),)
+	}
+
+	fn or(&mut self,) -> CompileResult<(),> {
+		let line = self.previous().line();
+		let else_jump = self.emit_jump(OpCode::JumpIfFalse, line,);
+		let end_jump = self.emit_jump(OpCode::Jump, line,);
+		self.patch_jump(else_jump,);
+		self.chunk.write_op(OpCode::Pop, line,);
+		self.parse_precedence(Precedence::Or,)?;
+		self.patch_jump(end_jump,);
+		Ok((),)
+	}
+
+	fn variable(&mut self, can_assign: bool,) -> CompileResult<(),> {
+		let name = self.previous().clone();
+		let constant = self.identifier_constant(name.lexeme(),)?;
+
+		if can_assign && self.match_tokens(&[Equal],) {
+			self.expression()?;
+			self.chunk.write_op(OpCode::SetGlobal, name.line(),);
+		} else {
+			self.chunk.write_op(OpCode::GetGlobal, name.line(),);
+		}
+		self.chunk.write_byte(constant, name.line(),);
+		Ok((),)
+	}
+
+	/// Emits a jump instruction with a placeholder 2-byte offset and
+	/// returns its position so it can be backpatched once the real
+	/// destination is known.
+	fn emit_jump(&mut self, op: OpCode, line: usize,) -> usize {
+		self.chunk.write_op(op, line,);
+		self.chunk.write_byte(0xff, line,);
+		self.chunk.write_byte(0xff, line,);
+		self.chunk.len() - 2
+	}
+
+	fn patch_jump(&mut self, offset: usize,) {
+		let jump = self.chunk.len() - offset - 2;
+		self.chunk.patch_byte(offset, (jump >> 8) as u8,);
+		self.chunk.patch_byte(offset + 1, jump as u8,);
+	}
+
+	fn emit_loop(&mut self, loop_start: usize, line: usize,) {
+		self.chunk.write_op(OpCode::Loop, line,);
+		let offset = self.chunk.len() - loop_start + 2;
+		self.chunk.write_byte((offset >> 8) as u8, line,);
+		self.chunk.write_byte(offset as u8, line,);
+	}
+
+	fn match_tokens(&mut self, types: &[TokenType],) -> bool {
+		for tt in types {
+			if self.check(tt.clone(),) {
+				self.advance();
+				return true;
+			}
+		}
+		false
+	}
+
+	fn consume(&mut self, tt: TokenType, what: String,) -> CompileResult<Token,> {
+		if self.check(tt,) {
+			return Ok(self.advance().clone(),);
+		}
+		let found = self.peek().token_type().clone();
+		let err = ErrorKind::ExpectedToken(what, found,);
+		Err(self.error(self.peek(), err,),)
+	}
+
+	fn check(&self, tt: TokenType,) -> bool { !self.at_end() && self.peek().token_type() == &tt }
+
+	fn advance(&mut self,) -> &Token {
+		if !self.at_end() {
+			let next = self.tokens.next().expect("token stream must yield an EOF token before ending",);
+			self.previous = Some(std::mem::replace(&mut self.current, next,),);
+		}
+		self.previous()
+	}
+
+	fn at_end(&self,) -> bool { self.peek().token_type() == &EOF }
+
+	fn peek(&self,) -> &Token { &self.current }
+
+	fn previous(&self,) -> &Token {
+		self.previous.as_ref().expect("previous() called before the first token was consumed",)
+	}
+
+	fn error(&self, token: &Token, kind: ErrorKind,) -> InterpreterError {
+		let err = InterpreterError::new().occur(kind,).at(token.span(),);
+		err_report::report(&self.src, &err,);
+		self.sink.push(err.clone(),);
+		err
+	}
+
+	/// Panic-mode recovery: discard tokens until we're positioned at a
+	/// likely statement boundary, so a single syntax error doesn't abort
+	/// compilation of the rest of the file.
+	fn synchronize(&mut self,) {
+		self.advance();
+
+		while !self.at_end() {
+			if self.previous().token_type() == &Semicolon {
+				return;
+			}
+
+			if matches!(self.peek().token_type(), Class | Fun | Var | For | If | While | Print | Return) {
+				return;
+			}
+
+			self.advance();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::scanner::Scanner;
+
+	fn compile(src: &str,) -> (Chunk, ErrorSink,) {
+		let src = src.to_string();
+		let tokens = Scanner::new(src.clone(),).scan_tokens();
+		let sink = ErrorSink::new();
+		let chunk = Compiler::new(tokens, src, sink.clone(),).compile();
+		(chunk, sink,)
+	}
+
+	#[test]
+	fn expression_statement_emits_constants_and_pop_test() {
+		let (chunk, sink,) = compile("1 + 2;",);
+		assert!(!sink.had_error());
+		assert_eq!(OpCode::try_from(chunk.read_byte(0,),).unwrap(), OpCode::Constant);
+		assert_eq!(OpCode::try_from(chunk.read_byte(2,),).unwrap(), OpCode::Constant);
+		assert_eq!(OpCode::try_from(chunk.read_byte(4,),).unwrap(), OpCode::Add);
+		assert_eq!(OpCode::try_from(chunk.read_byte(5,),).unwrap(), OpCode::Pop);
+		assert_eq!(OpCode::try_from(chunk.read_byte(6,),).unwrap(), OpCode::Return);
+		assert_eq!(chunk.len(), 7);
+	}
+
+	#[test]
+	fn var_declaration_emits_define_global_test() {
+		let (chunk, sink,) = compile("var a = 1;",);
+		assert!(!sink.had_error());
+		assert_eq!(OpCode::try_from(chunk.read_byte(0,),).unwrap(), OpCode::Constant);
+		assert_eq!(OpCode::try_from(chunk.read_byte(2,),).unwrap(), OpCode::DefineGlobal);
+		assert_eq!(OpCode::try_from(chunk.read_byte(4,),).unwrap(), OpCode::Return);
+	}
+
+	/// Exercises `if`/`else`'s jump backpatching: the `JumpIfFalse` must land
+	/// exactly on the else branch, and the unconditional `Jump` must land
+	/// exactly past it, with no leftover `0xff` placeholder bytes.
+	#[test]
+	fn if_else_patches_jumps_test() {
+		let (chunk, sink,) = compile("if (true) print 1; else print 2;",);
+		assert!(!sink.had_error());
+		assert_eq!(OpCode::try_from(chunk.read_byte(0,),).unwrap(), OpCode::True);
+		assert_eq!(OpCode::try_from(chunk.read_byte(1,),).unwrap(), OpCode::JumpIfFalse);
+		let then_offset = u16::from_be_bytes([chunk.read_byte(2,), chunk.read_byte(3,)],) as usize;
+		assert_eq!(OpCode::try_from(chunk.read_byte(4 + then_offset,),).unwrap(), OpCode::Pop);
+		assert_eq!(OpCode::try_from(chunk.read_byte(8,),).unwrap(), OpCode::Jump);
+		let else_offset = u16::from_be_bytes([chunk.read_byte(9,), chunk.read_byte(10,)],) as usize;
+		assert_eq!(OpCode::try_from(chunk.read_byte(11 + else_offset,),).unwrap(), OpCode::Return);
+	}
+
+	/// Exercises `while`'s loop backpatching: `Loop` must jump backward to
+	/// the start of the condition, and the exit `JumpIfFalse` must land
+	/// exactly on the loop's trailing `Pop`.
+	#[test]
+	fn while_loop_patches_jumps_test() {
+		let (chunk, sink,) = compile("while (false) print 1;",);
+		assert!(!sink.had_error());
+		assert_eq!(OpCode::try_from(chunk.read_byte(8,),).unwrap(), OpCode::Loop);
+		let loop_offset = u16::from_be_bytes([chunk.read_byte(9,), chunk.read_byte(10,)],) as usize;
+		assert_eq!(11 - loop_offset, 0);
+		let exit_offset = u16::from_be_bytes([chunk.read_byte(2,), chunk.read_byte(3,)],) as usize;
+		assert_eq!(OpCode::try_from(chunk.read_byte(4 + exit_offset,),).unwrap(), OpCode::Pop);
+	}
+
+	#[test]
+	fn too_many_constants_reports_compile_error_test() {
+		let src = (0..300).map(|i| format!("var v{i} = {i};"),).collect::<String,>();
+		let (_chunk, sink,) = compile(&src,);
+		assert!(sink.had_error());
+	}
+}
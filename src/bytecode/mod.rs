@@ -0,0 +1,9 @@
+//! Bytecode backend: compiles source straight to bytecode and runs it on a
+//! stack-based VM, as an alternative to the tree-walking [`crate::interpreter`].
+
+pub mod chunk;
+pub mod compiler;
+pub mod vm;
+
+pub use compiler::Compiler;
+pub use vm::VM;
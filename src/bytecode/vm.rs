@@ -0,0 +1,221 @@
+//! A stack-based virtual machine that executes a compiled [`Chunk`].
+
+use std::collections::HashMap;
+
+use crate::bytecode::chunk::Chunk;
+use crate::bytecode::chunk::OpCode;
+use crate::err_report;
+use crate::err_report::ErrorKind;
+use crate::err_report::ErrorSink;
+use crate::err_report::InterpreterError;
+use crate::interpreter::Value;
+use crate::token::Span;
+
+type RunResult<T,> = Result<T, InterpreterError,>;
+
+/// Executes one [`Chunk`] at a time against a value stack and a table of
+/// global variables, mirroring [`crate::interpreter::Interpreter`]'s
+/// error-reporting style but operating on bytes instead of an AST.
+pub struct VM {
+	chunk:   Chunk,
+	ip:      usize,
+	line:    usize,
+	stack:   Vec<Value,>,
+	globals: HashMap<String, Value,>,
+	src:     String,
+	sink:    ErrorSink,
+}
+
+impl VM {
+	pub fn new(src: String, sink: ErrorSink,) -> Self {
+		Self {
+			chunk: Chunk::new(),
+			ip: 0,
+			line: 0,
+			stack: Vec::new(),
+			globals: HashMap::new(),
+			src,
+			sink,
+		}
+	}
+
+	/// Runs `chunk` to completion, stopping early if a runtime error occurs.
+	pub fn run(&mut self, chunk: Chunk,) {
+		self.chunk = chunk;
+		self.ip = 0;
+		self.line = 0;
+		self.stack.clear();
+		let _ = self.execute();
+	}
+
+	fn execute(&mut self,) -> RunResult<(),> {
+		loop {
+			let op = self.read_op();
+			match op {
+				OpCode::Constant => {
+					let constant = self.read_constant();
+					self.stack.push(constant,);
+				},
+				OpCode::Nil => self.stack.push(Value::Nil,),
+				OpCode::True => self.stack.push(Value::Bool(true,),),
+				OpCode::False => self.stack.push(Value::Bool(false,),),
+				OpCode::Pop => {
+					self.pop()?;
+				},
+				OpCode::Add => self.binary_op("operands must be two numbers or two strings", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Number(a + b,),),
+					(Value::Str(a,), Value::Str(b,),) => Some(Value::Str(a + &b,),),
+					_ => None,
+				},)?,
+				OpCode::Subtract => self.binary_op("operands must be numbers", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Number(a - b,),),
+					_ => None,
+				},)?,
+				OpCode::Multiply => self.binary_op("operands must be numbers", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Number(a * b,),),
+					_ => None,
+				},)?,
+				OpCode::Divide => self.binary_op("operands must be numbers", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Number(a / b,),),
+					_ => None,
+				},)?,
+				OpCode::Greater => self.binary_op("operands must be numbers", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Bool(a > b,),),
+					_ => None,
+				},)?,
+				OpCode::Less => self.binary_op("operands must be numbers", |a, b| match (a, b,) {
+					(Value::Number(a,), Value::Number(b,),) => Some(Value::Bool(a < b,),),
+					_ => None,
+				},)?,
+				OpCode::Equal => {
+					let b = self.pop()?;
+					let a = self.pop()?;
+					self.stack.push(Value::Bool(a == b,),);
+				},
+				OpCode::Negate => match self.pop()? {
+					Value::Number(n,) => self.stack.push(Value::Number(-n,),),
+					_ => {
+						let kind = ErrorKind::TypeMismatch("operand must be a number".to_string(),);
+						return Err(self.runtime_error(kind,),);
+					},
+				},
+				OpCode::Print => {
+					let v = self.pop()?;
+					println!("{v}");
+				},
+				OpCode::DefineGlobal => {
+					let name = self.read_constant_name();
+					let value = self.pop()?;
+					self.globals.insert(name, value,);
+				},
+				OpCode::GetGlobal => {
+					let name = self.read_constant_name();
+					match self.globals.get(&name,) {
+						Some(v,) => self.stack.push(v.clone(),),
+						None => return Err(self.runtime_error(ErrorKind::UndefinedVariable(name,),),),
+					}
+				},
+				OpCode::SetGlobal => {
+					let name = self.read_constant_name();
+					let value = self.peek(0,)?.clone();
+					if !self.globals.contains_key(&name,) {
+						return Err(self.runtime_error(ErrorKind::UndefinedVariable(name,),),);
+					}
+					self.globals.insert(name, value,);
+				},
+				OpCode::Jump => {
+					let offset = self.read_short();
+					self.ip += offset as usize;
+				},
+				OpCode::JumpIfFalse => {
+					let offset = self.read_short();
+					if !self.peek(0,)?.truthy() {
+						self.ip += offset as usize;
+					}
+				},
+				OpCode::Loop => {
+					let offset = self.read_short();
+					self.ip -= offset as usize;
+				},
+				OpCode::Assert => {
+					let v = self.pop()?;
+					if !v.truthy() {
+						return Err(self.runtime_error(ErrorKind::AssertionFailed,),);
+					}
+				},
+				OpCode::Return => return Ok((),),
+			}
+		}
+	}
+
+	fn binary_op(&mut self, expected: &str, f: impl FnOnce(Value, Value,) -> Option<Value,>,) -> RunResult<(),> {
+		let b = self.pop()?;
+		let a = self.pop()?;
+		match f(a, b,) {
+			Some(v,) => {
+				self.stack.push(v,);
+				Ok((),)
+			},
+			None => {
+				let kind = ErrorKind::TypeMismatch(expected.to_string(),);
+				Err(self.runtime_error(kind,),)
+			},
+		}
+	}
+
+	fn pop(&mut self,) -> RunResult<Value,> {
+		self.stack.pop().ok_or_else(|| self.runtime_error(ErrorKind::Unknown,),)
+	}
+
+	fn peek(&self, back: usize,) -> RunResult<&Value,> {
+		let len = self.stack.len();
+		if back >= len {
+			return Err(self.runtime_error(ErrorKind::Unknown,),);
+		}
+		Ok(&self.stack[len - 1 - back],)
+	}
+
+	fn read_op(&mut self,) -> OpCode {
+		let byte = self.chunk.read_byte(self.ip,);
+		self.line = self.chunk.line_at(self.ip,);
+		self.ip += 1;
+		OpCode::try_from(byte,).expect("bytecode stream holds only bytes written by the compiler",)
+	}
+
+	fn read_byte(&mut self,) -> u8 {
+		let byte = self.chunk.read_byte(self.ip,);
+		self.ip += 1;
+		byte
+	}
+
+	fn read_short(&mut self,) -> u16 {
+		let hi = self.read_byte();
+		let lo = self.read_byte();
+		u16::from_be_bytes([hi, lo],)
+	}
+
+	fn read_constant(&mut self,) -> Value {
+		let index = self.read_byte();
+		self.chunk.read_constant(index,)
+	}
+
+	/// Reads a constant the compiler emitted to name a global variable.
+	fn read_constant_name(&mut self,) -> String {
+		match self.read_constant() {
+			Value::Str(name,) => name,
+			_ => unreachable!("global names are always compiled as string constants"),
+		}
+	}
+
+	/// Reports a runtime error against the source line the current
+	/// instruction came from. Bytecode offsets don't carry column/byte-range
+	/// information the way AST tokens do, so the underline always starts at
+	/// column 1 instead of the exact offending text.
+	fn runtime_error(&self, kind: ErrorKind,) -> InterpreterError {
+		let span = Span { line: self.line, column: 1, start: 0, end: 0, };
+		let err = InterpreterError::new().occur(kind,).at(span,);
+		err_report::report(&self.src, &err,);
+		self.sink.push(err.clone(),);
+		err
+	}
+}
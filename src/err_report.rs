@@ -1,9 +1,16 @@
 //! Error reporting module
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::token::Span;
+use crate::token::TokenType;
+
 #[derive(Debug, Clone,)]
 pub struct InterpreterError {
 	kind:    ErrorKind,
 	had_err: bool,
+	span:    Option<Span,>,
 }
 impl std::error::Error for InterpreterError {}
 
@@ -12,13 +19,19 @@ impl std::fmt::Display for InterpreterError {
 }
 
 impl InterpreterError {
-	pub fn new() -> Self { Self { kind: ErrorKind::Unknown, had_err: false, } }
+	pub fn new() -> Self { Self { kind: ErrorKind::Unknown, had_err: false, span: None, } }
 
 	pub fn occur(mut self, e: ErrorKind,) -> Self {
 		self.had_err = true;
 		self.kind = e;
 		self
 	}
+
+	/// Attaches the source span the error should be reported against.
+	pub fn at(mut self, span: Span,) -> Self {
+		self.span = Some(span,);
+		self
+	}
 }
 
 #[derive(Debug, Clone,)]
@@ -26,6 +39,17 @@ pub enum ErrorKind {
 	UnexpectedCharacter(char,),
 	UnterminatedString(String,),
 	UnterminatedComment,
+	UnterminatedChar(String,),
+	EmptyChar,
+	CharTooLong(String,),
+	ExpectedToken(String, TokenType,),
+	InvalidAssignmentTarget,
+	InvalidEscape(char,),
+	TypeMismatch(String,),
+	UndefinedVariable(String,),
+	NotCallable,
+	AssertionFailed,
+	TooManyConstants,
 	Unknown,
 	//NaE,  Not an Error
 }
@@ -35,13 +59,52 @@ impl std::fmt::Display for ErrorKind {
 			ErrorKind::UnexpectedCharacter(c,) => write!(f, "Unexpected character: {c}",),
 			ErrorKind::UnterminatedString(s,) => write!(f, "Unterminated string: {s}",),
 			ErrorKind::UnterminatedComment => write!(f, "Unterminated comment",),
+			ErrorKind::UnterminatedChar(s,) => write!(f, "Unterminated character literal: {s}",),
+			ErrorKind::EmptyChar => write!(f, "Empty character literal",),
+			ErrorKind::CharTooLong(s,) => write!(f, "Too many characters in character literal: {s}",),
+			ErrorKind::ExpectedToken(expected, found,) => {
+				write!(f, "Expected {expected}, found `{found}`",)
+			},
+			ErrorKind::InvalidAssignmentTarget => write!(f, "Invalid assignment target",),
+			ErrorKind::InvalidEscape(c,) => write!(f, "Invalid escape sequence: \\{c}",),
+			ErrorKind::TypeMismatch(msg,) => write!(f, "Type mismatch: {msg}",),
+			ErrorKind::UndefinedVariable(name,) => write!(f, "Undefined variable '{name}'",),
+			ErrorKind::NotCallable => write!(f, "Can only call functions and classes",),
+			ErrorKind::AssertionFailed => write!(f, "Assertion failed",),
+			ErrorKind::TooManyConstants => write!(f, "Too many constants in one chunk",),
 			ErrorKind::Unknown => write!(f, "Unknown error",),
 		}
 	}
 }
 
-pub fn error(line: usize, err: impl std::error::Error,) { report(line, "".to_string(), err,) }
+/// Reports an error, pointing at its source span with a `rustc`-style caret
+/// underline when one is attached, falling back to a plain message otherwise.
+pub fn report(src: &str, err: &InterpreterError,) {
+	match err.span {
+		Some(span,) => {
+			let line_text = src.lines().nth(span.line - 1,).unwrap_or("",);
+			let width = (span.end - span.start).max(1,);
+			eprintln!("\u{ea87} Error at line {}, column {} \u{ea87}", span.line, span.column,);
+			eprintln!("{line_text}");
+			eprintln!("{}{}", " ".repeat(span.column - 1,), "^".repeat(width,),);
+			eprintln!("{err}",);
+		},
+		None => eprintln!("\u{ea87} Error \u{ea87}\n{err}",),
+	}
+}
+
+/// A sink shared across the scanner, parser, and interpreter so a single
+/// pass can accumulate every error it hits instead of the last one
+/// clobbering the rest, matching the book's `hadError` gate.
+#[derive(Debug, Clone, Default,)]
+pub struct ErrorSink {
+	errors: Rc<RefCell<Vec<InterpreterError,>,>,>,
+}
+
+impl ErrorSink {
+	pub fn new() -> Self { Self::default() }
+
+	pub fn push(&self, err: InterpreterError,) { self.errors.borrow_mut().push(err,); }
 
-pub fn report(line: usize, place: String, err: impl std::error::Error,) {
-	eprintln!("\u{ea87} Error at line {line} where {place} \u{ea87}\n{err}",);
+	pub fn had_error(&self,) -> bool { !self.errors.borrow().is_empty() }
 }
@@ -0,0 +1,348 @@
+//! Tree-walk interpreter that evaluates the AST produced by the parser.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::err_report;
+use crate::err_report::ErrorKind;
+use crate::err_report::ErrorSink;
+use crate::err_report::InterpreterError;
+use crate::parser::Expr;
+use crate::parser::Literal;
+use crate::parser::Stmt;
+use crate::token::Token;
+use crate::token::TokenType;
+
+#[derive(Debug, Clone, PartialEq,)]
+pub enum Value {
+	Number(f64,),
+	Str(String,),
+	Char(char,),
+	Bool(bool,),
+	Nil,
+}
+
+impl Value {
+	/// Lox truthiness: `nil` and `false` are falsey, everything else is truthy.
+	pub(crate) fn truthy(&self,) -> bool { !matches!(self, Value::Nil | Value::Bool(false)) }
+}
+
+impl From<&Literal,> for Value {
+	fn from(l: &Literal,) -> Self {
+		match l {
+			Literal::Number(n,) => Value::Number(*n,),
+			Literal::Str(s,) => Value::Str(s.clone(),),
+			Literal::Char(c,) => Value::Char(*c,),
+			Literal::Bool(b,) => Value::Bool(*b,),
+			Literal::Nil => Value::Nil,
+		}
+	}
+}
+
+impl std::fmt::Display for Value {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_,>,) -> std::fmt::Result {
+		match self {
+			Value::Number(n,) => {
+				if n.fract() == 0.0 && n.is_finite() {
+					write!(f, "{}", *n as i64)
+				} else {
+					write!(f, "{n}")
+				}
+			},
+			Value::Str(s,) => write!(f, "{s}"),
+			Value::Char(c,) => write!(f, "{c}"),
+			Value::Bool(b,) => write!(f, "{b}"),
+			Value::Nil => write!(f, "nil"),
+		}
+	}
+}
+
+/// A lexical scope mapping names to values, with an optional parent so
+/// nested blocks can shadow and still reach enclosing variables.
+#[derive(Debug,)]
+pub struct Environment {
+	values: HashMap<String, Value,>,
+	parent: Option<Rc<RefCell<Environment,>>,>,
+}
+
+impl Environment {
+	pub fn new() -> Self { Self { values: HashMap::new(), parent: None, } }
+
+	pub fn with_parent(parent: Rc<RefCell<Environment,>>,) -> Self {
+		Self { values: HashMap::new(), parent: Some(parent,), }
+	}
+
+	pub fn define(&mut self, name: String, value: Value,) { self.values.insert(name, value,); }
+
+	pub fn get(&self, name: &str,) -> Option<Value,> {
+		match self.values.get(name,) {
+			Some(v,) => Some(v.clone(),),
+			None => self.parent.as_ref().and_then(|p| p.borrow().get(name,)),
+		}
+	}
+
+	pub fn assign(&mut self, name: &str, value: Value,) -> bool {
+		if self.values.contains_key(name,) {
+			self.values.insert(name.to_string(), value,);
+			true
+		} else if let Some(parent,) = &self.parent {
+			parent.borrow_mut().assign(name, value,)
+		} else {
+			false
+		}
+	}
+}
+
+type RuntimeResult<T,> = Result<T, InterpreterError,>;
+
+pub struct Interpreter {
+	env:  Rc<RefCell<Environment,>>,
+	src:  String,
+	sink: ErrorSink,
+}
+
+impl Interpreter {
+	pub fn new(src: String, sink: ErrorSink,) -> Self {
+		Self { env: Rc::new(RefCell::new(Environment::new(),),), src, sink, }
+	}
+
+	/// Executes every statement. Runtime errors are reported at the point
+	/// they're raised (so the caret points at the offending token); this
+	/// loop just stops the current statement and moves on to the next one.
+	pub fn interpret(&mut self, stmts: Vec<Stmt,>,) {
+		for stmt in &stmts {
+			let _ = self.execute(stmt,);
+		}
+	}
+
+	fn execute(&mut self, stmt: &Stmt,) -> RuntimeResult<(),> {
+		match stmt {
+			Stmt::Expression(e,) => {
+				self.evaluate(e,)?;
+				Ok((),)
+			},
+			Stmt::Print(e,) => {
+				let v = self.evaluate(e,)?;
+				println!("{v}");
+				Ok((),)
+			},
+			Stmt::Assert(e,) => {
+				let v = self.evaluate(e,)?;
+				if v.truthy() {
+					Ok((),)
+				} else {
+					Err(self.unspanned_error(ErrorKind::AssertionFailed,),)
+				}
+			},
+			Stmt::Var(name, init,) => {
+				let value = match init {
+					Some(e,) => self.evaluate(e,)?,
+					None => Value::Nil,
+				};
+				self.env.borrow_mut().define(name.lexeme().to_string(), value,);
+				Ok((),)
+			},
+			Stmt::Block(stmts,) => {
+				let child = Rc::new(RefCell::new(Environment::with_parent(self.env.clone(),),),);
+				self.execute_block(stmts, child,)
+			},
+			Stmt::If(cond, then_branch, else_branch,) => {
+				if self.evaluate(cond,)?.truthy() {
+					self.execute(then_branch,)
+				} else if let Some(else_branch,) = else_branch {
+					self.execute(else_branch,)
+				} else {
+					Ok((),)
+				}
+			},
+			Stmt::While(cond, body,) => {
+				while self.evaluate(cond,)?.truthy() {
+					self.execute(body,)?;
+				}
+				Ok((),)
+			},
+		}
+	}
+
+	fn execute_block(&mut self, stmts: &[Stmt], env: Rc<RefCell<Environment,>>,) -> RuntimeResult<(),> {
+		let prev = std::mem::replace(&mut self.env, env,);
+		let result = stmts.iter().try_for_each(|s| self.execute(s,),);
+		self.env = prev;
+		result
+	}
+
+	fn evaluate(&mut self, expr: &Expr,) -> RuntimeResult<Value,> {
+		match expr {
+			Expr::Literal(l,) => Ok(Value::from(l,),),
+			Expr::Grouping(e,) => self.evaluate(e,),
+			Expr::Unary(op, e,) => self.eval_unary(op, e,),
+			Expr::Binary(l, op, r,) => self.eval_binary(l, op, r,),
+			Expr::Logical(l, op, r,) => self.eval_logical(l, op, r,),
+			Expr::Variable(name,) => {
+				let found = self.env.borrow().get(name.lexeme(),);
+				found.ok_or_else(|| {
+					let kind = ErrorKind::UndefinedVariable(name.lexeme().to_string(),);
+					self.runtime_error(name, kind,)
+				},)
+			},
+			Expr::Assign(name, e,) => {
+				let value = self.evaluate(e,)?;
+				if self.env.borrow_mut().assign(name.lexeme(), value.clone(),) {
+					Ok(value,)
+				} else {
+					let kind = ErrorKind::UndefinedVariable(name.lexeme().to_string(),);
+					Err(self.runtime_error(name, kind,),)
+				}
+			},
+			Expr::Call(callee, paren, args,) => {
+				self.evaluate(callee,)?;
+				for arg in args {
+					self.evaluate(arg,)?;
+				}
+				Err(self.runtime_error(paren, ErrorKind::NotCallable,),)
+			},
+		}
+	}
+
+	fn eval_unary(&mut self, op: &Token, e: &Expr,) -> RuntimeResult<Value,> {
+		let v = self.evaluate(e,)?;
+		match op.token_type() {
+			TokenType::Minus => match v {
+				Value::Number(n,) => Ok(Value::Number(-n,),),
+				_ => {
+					let kind = ErrorKind::TypeMismatch("operand must be a number".to_string(),);
+					Err(self.runtime_error(op, kind,),)
+				},
+			},
+			TokenType::Bang => Ok(Value::Bool(!v.truthy(),),),
+			_ => {
+				let kind = ErrorKind::TypeMismatch("unsupported unary operator".to_string(),);
+				Err(self.runtime_error(op, kind,),)
+			},
+		}
+	}
+
+	fn eval_logical(&mut self, l: &Expr, op: &Token, r: &Expr,) -> RuntimeResult<Value,> {
+		let left = self.evaluate(l,)?;
+		match op.token_type() {
+			TokenType::Or if left.truthy() => Ok(left,),
+			TokenType::And if !left.truthy() => Ok(left,),
+			_ => self.evaluate(r,),
+		}
+	}
+
+	fn eval_binary(&mut self, l: &Expr, op: &Token, r: &Expr,) -> RuntimeResult<Value,> {
+		let left = self.evaluate(l,)?;
+		let right = self.evaluate(r,)?;
+
+		use TokenType::*;
+		match (op.token_type(), left, right,) {
+			(Plus, Value::Number(a,), Value::Number(b,),) => Ok(Value::Number(a + b,),),
+			(Plus, Value::Str(a,), Value::Str(b,),) => Ok(Value::Str(a + &b,),),
+			(Minus, Value::Number(a,), Value::Number(b,),) => Ok(Value::Number(a - b,),),
+			(Star, Value::Number(a,), Value::Number(b,),) => Ok(Value::Number(a * b,),),
+			(Slash, Value::Number(a,), Value::Number(b,),) => Ok(Value::Number(a / b,),),
+			(Mod, Value::Number(a,), Value::Number(b,),) => Ok(Value::Number(a % b,),),
+			(Greater, Value::Number(a,), Value::Number(b,),) => Ok(Value::Bool(a > b,),),
+			(GreaterEqual, Value::Number(a,), Value::Number(b,),) => Ok(Value::Bool(a >= b,),),
+			(Less, Value::Number(a,), Value::Number(b,),) => Ok(Value::Bool(a < b,),),
+			(LessEqual, Value::Number(a,), Value::Number(b,),) => Ok(Value::Bool(a <= b,),),
+			(EqualEqual, a, b,) => Ok(Value::Bool(a == b,),),
+			(BangEqual, a, b,) => Ok(Value::Bool(a != b,),),
+			(Plus, ..) => {
+				let kind = ErrorKind::TypeMismatch("operands must be two numbers or two strings".to_string(),);
+				Err(self.runtime_error(op, kind,),)
+			},
+			_ => {
+				let kind = ErrorKind::TypeMismatch("operands must be numbers".to_string(),);
+				Err(self.runtime_error(op, kind,),)
+			},
+		}
+	}
+
+	fn runtime_error(&self, token: &Token, kind: ErrorKind,) -> InterpreterError {
+		let err = InterpreterError::new().occur(kind,).at(token.span(),);
+		err_report::report(&self.src, &err,);
+		self.sink.push(err.clone(),);
+		err
+	}
+
+	fn unspanned_error(&self, kind: ErrorKind,) -> InterpreterError {
+		let err = InterpreterError::new().occur(kind,);
+		err_report::report(&self.src, &err,);
+		self.sink.push(err.clone(),);
+		err
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parser::Parser;
+	use crate::scanner::Scanner;
+
+	fn run(src: &str,) -> (Interpreter, ErrorSink,) {
+		let src = src.to_string();
+		let tokens = Scanner::new(src.clone(),).scan_tokens();
+		let sink = ErrorSink::new();
+		let stmts = Parser::new(tokens, src.clone(), sink.clone(),).parse();
+		let mut interp = Interpreter::new(src, sink.clone(),);
+		interp.interpret(stmts,);
+		(interp, sink,)
+	}
+
+	fn var(interp: &Interpreter, name: &str,) -> Value {
+		interp.env.borrow().get(name,).expect("variable should be defined",)
+	}
+
+	#[test]
+	fn arithmetic_precedence_test() {
+		let (interp, sink,) = run("var x = 1 + 2 * 3;",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "x",), Value::Number(7.0,));
+	}
+
+	#[test]
+	fn string_concat_test() {
+		let (interp, sink,) = run("var s = \"foo\" + \"bar\";",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "s",), Value::Str("foobar".to_string(),));
+	}
+
+	#[test]
+	fn truthy_test() {
+		let (interp, sink,) = run("var a = !false; var b = !0;",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "a",), Value::Bool(true,));
+		// Lox truthiness: only `nil` and `false` are falsey, so `0` is truthy.
+		assert_eq!(var(&interp, "b",), Value::Bool(false,));
+	}
+
+	#[test]
+	fn if_else_test() {
+		let (interp, sink,) = run("var x = 0; if (true) { x = 1; } else { x = 2; }",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "x",), Value::Number(1.0,));
+	}
+
+	#[test]
+	fn while_loop_test() {
+		let (interp, sink,) = run("var x = 0; while (x < 3) { x = x + 1; }",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "x",), Value::Number(3.0,));
+	}
+
+	#[test]
+	fn block_scoping_does_not_leak_test() {
+		let (interp, sink,) = run("var x = 1; { var x = 2; }",);
+		assert!(!sink.had_error());
+		assert_eq!(var(&interp, "x",), Value::Number(1.0,));
+	}
+
+	#[test]
+	fn undefined_variable_reports_error_test() {
+		let (_interp, sink,) = run("print y;",);
+		assert!(sink.had_error());
+	}
+}
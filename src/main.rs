@@ -1,15 +1,35 @@
 #![feature(step_trait)]
 
+mod bytecode;
 mod err_report;
+mod interpreter;
+mod parser;
 mod scanner;
 mod token;
 
+use bytecode::Compiler;
+use bytecode::VM;
+use interpreter::Interpreter;
+use parser::Parser;
 use scanner::Scanner;
 use std::io;
 
-fn run_file(path: String,) -> io::Result<(),> { run(std::fs::read_to_string(path,)?,) }
+/// Flags recognized anywhere among the arguments, alongside the (optional)
+/// script path.
+#[derive(Debug, Clone, Copy, Default,)]
+struct Flags {
+	use_vm:      bool,
+	show_tokens: bool,
+	show_ast:    bool,
+}
+
+/// Runs a whole file and reports whether it had any errors, so `main` can
+/// exit with a non-zero status (matching the book's `hadError` gate).
+fn run_file(path: String, flags: Flags,) -> io::Result<bool,> {
+	run(std::fs::read_to_string(path,)?, flags,)
+}
 
-fn run_prompt() -> io::Result<(),> {
+fn run_prompt(flags: Flags,) -> io::Result<bool,> {
 	let input = std::io::stdin();
 	loop {
 		let mut line = String::new();
@@ -17,33 +37,87 @@ fn run_prompt() -> io::Result<(),> {
 		if input.read_line(&mut line,)? == 0 {
 			break;
 		}
-		let _ = run(line,);
+		// a bad line shouldn't kill the REPL, so its error status is ignored
+		let _ = run(line, flags,);
 		//		line.clear();
 	}
-	Ok((),)
+	Ok(false,)
 }
 
-fn run(src: String,) -> io::Result<(),> {
-	let scan = Scanner::new(src,);
+/// Scans, then either dumps an intermediate stage (`-t`/`-a`), compiles +
+/// runs the bytecode VM, or parses + interprets the AST, stopping early at
+/// the first stage that recorded an error instead of feeding broken input
+/// forward.
+///
+/// The `--vm` path feeds the scanner straight into `Compiler` as an
+/// iterator instead of collecting it into a `Vec<Token>` first: scanning
+/// and compiling happen as a single pull-based pass, so a lexical error
+/// surfaces through the same post-compile `sink.had_error()` check as a
+/// syntax error would.
+fn run(src: String, flags: Flags,) -> io::Result<bool,> {
+	let scan = Scanner::new(src.clone(),);
+	let sink = scan.sink();
+
+	if flags.show_tokens {
+		let tokens = scan.scan_tokens();
+		println!("{tokens:#?}");
+		return Ok(sink.had_error(),);
+	}
+
+	if flags.use_vm {
+		let chunk = Compiler::new(scan, src.clone(), sink.clone(),).compile();
+		if sink.had_error() {
+			return Ok(true,);
+		}
+		VM::new(src, sink.clone(),).run(chunk,);
+		return Ok(sink.had_error(),);
+	}
+
 	let tokens = scan.scan_tokens();
+	if sink.had_error() {
+		return Ok(true,);
+	}
 
-	// d: currently, just print the tokens
-	for token in tokens {
-		println!("{token:?}",);
+	if flags.show_ast {
+		let stmts = Parser::new(tokens, src.clone(), sink.clone(),).parse();
+		println!("{stmts:#?}");
+		return Ok(sink.had_error(),);
 	}
-	Ok((),)
+
+	let stmts = Parser::new(tokens, src.clone(), sink.clone(),).parse();
+	if sink.had_error() {
+		return Ok(true,);
+	}
+
+	Interpreter::new(src, sink.clone(),).interpret(stmts,);
+	Ok(sink.had_error(),)
 }
 
 fn main() -> io::Result<(),> {
-	let mut args = std::env::args();
-	if args.len() > 1 {
-		println!("Usage: lox [script]");
-		Ok((),)
-	} else if args.len() == 1 {
-		run_file(args.next().expect("🫠main: cmdline arg not found",),)
+	let args: Vec<String,> = std::env::args().skip(1,).collect();
+	let flags = Flags {
+		use_vm:      args.iter().any(|a| a == "--vm",),
+		show_tokens: args.iter().any(|a| a == "-t" || a == "--tokens",),
+		show_ast:    args.iter().any(|a| a == "-a" || a == "--ast",),
+	};
+	let script: Vec<&String,> = args
+		.iter()
+		.filter(|a| !matches!(a.as_str(), "--vm" | "-t" | "--tokens" | "-a" | "--ast"),)
+		.collect();
+
+	let had_error = if script.len() > 1 {
+		println!("Usage: lox [--vm] [-t|--tokens] [-a|--ast] [script]");
+		false
+	} else if let Some(path,) = script.first() {
+		run_file(path.to_string(), flags,)?
 	} else {
-		run_prompt()
+		run_prompt(flags,)?
+	};
+
+	if had_error {
+		std::process::exit(65);
 	}
+	Ok((),)
 }
 
 #[cfg(test)]
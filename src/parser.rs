@@ -0,0 +1,512 @@
+//! Recursive-descent parser turning the token stream into an `Expr`/`Stmt` AST.
+
+use crate::err_report;
+use crate::err_report::ErrorKind;
+use crate::err_report::ErrorSink;
+use crate::err_report::InterpreterError;
+use crate::token::Token;
+use crate::token::TokenType;
+use crate::token::TokenType::*;
+
+#[derive(Debug, Clone, PartialEq,)]
+pub enum Literal {
+	Number(f64,),
+	Str(String,),
+	Char(char,),
+	Bool(bool,),
+	Nil,
+}
+
+#[derive(Debug, Clone, PartialEq,)]
+pub enum Expr {
+	Literal(Literal,),
+	Grouping(Box<Expr,>,),
+	Unary(Token, Box<Expr,>,),
+	Binary(Box<Expr,>, Token, Box<Expr,>,),
+	Logical(Box<Expr,>, Token, Box<Expr,>,),
+	Variable(Token,),
+	Assign(Token, Box<Expr,>,),
+	Call(Box<Expr,>, Token, Vec<Expr,>,),
+}
+
+#[derive(Debug, Clone, PartialEq,)]
+pub enum Stmt {
+	Expression(Expr,),
+	Print(Expr,),
+	Assert(Expr,),
+	Var(Token, Option<Expr,>,),
+	Block(Vec<Stmt,>,),
+	If(Expr, Box<Stmt,>, Option<Box<Stmt,>>,),
+	While(Expr, Box<Stmt,>,),
+}
+
+/// A recursive-descent parser consuming a [`Vec<Token>`] via a cursor.
+///
+/// The grammar is implemented as a chain of methods, one per precedence
+/// level, from [`Self::expression`] (loosest) down to [`Self::primary`]
+/// (tightest): `expression -> assignment -> logic_or -> logic_and ->
+/// equality -> comparison -> term -> factor -> unary -> call -> primary`.
+pub struct Parser {
+	tokens:  Vec<Token,>,
+	current: usize,
+	src:     String,
+	sink:    ErrorSink,
+}
+
+type ParseResult<T,> = Result<T, InterpreterError,>;
+
+impl Parser {
+	pub fn new(tokens: Vec<Token,>, src: String, sink: ErrorSink,) -> Self {
+		Self { tokens, current: 0, src, sink, }
+	}
+
+	/// Parses the whole token stream into a list of statements, recovering
+	/// from syntax errors at statement boundaries so multiple independent
+	/// errors can be reported in a single pass.
+	pub fn parse(&mut self,) -> Vec<Stmt,> {
+		let mut stmts = vec![];
+		while !self.at_end() {
+			if let Some(stmt,) = self.declaration() {
+				stmts.push(stmt,);
+			}
+		}
+		stmts
+	}
+
+	fn declaration(&mut self,) -> Option<Stmt,> {
+		let stmt =
+			if self.match_tokens(&[Var],) { self.var_declaration() } else { self.statement() };
+
+		match stmt {
+			Ok(stmt,) => Some(stmt,),
+			Err(_,) => {
+				self.synchronize();
+				None
+			},
+		}
+	}
+
+	fn var_declaration(&mut self,) -> ParseResult<Stmt,> {
+		let name = self.consume(Identifier, "variable name".to_string(),)?;
+		let init = if self.match_tokens(&[Equal],) { Some(self.expression()?,) } else { None };
+		self.consume(Semicolon, "';' after variable declaration".to_string(),)?;
+		Ok(Stmt::Var(name, init,),)
+	}
+
+	fn statement(&mut self,) -> ParseResult<Stmt,> {
+		if self.match_tokens(&[Print],) {
+			self.print_statement()
+		} else if self.match_tokens(&[Assert],) {
+			self.assert_statement()
+		} else if self.match_tokens(&[If],) {
+			self.if_statement()
+		} else if self.match_tokens(&[While],) {
+			self.while_statement()
+		} else if self.match_tokens(&[For],) {
+			self.for_statement()
+		} else if self.match_tokens(&[LeftBrace],) {
+			Ok(Stmt::Block(self.block()?,),)
+		} else {
+			self.expression_statement()
+		}
+	}
+
+	fn print_statement(&mut self,) -> ParseResult<Stmt,> {
+		let value = self.expression()?;
+		self.consume(Semicolon, "';' after value".to_string(),)?;
+		Ok(Stmt::Print(value,),)
+	}
+
+	fn assert_statement(&mut self,) -> ParseResult<Stmt,> {
+		let value = self.expression()?;
+		self.consume(Semicolon, "';' after value".to_string(),)?;
+		Ok(Stmt::Assert(value,),)
+	}
+
+	fn if_statement(&mut self,) -> ParseResult<Stmt,> {
+		self.consume(LeftParen, "'(' after 'if'".to_string(),)?;
+		let cond = self.expression()?;
+		self.consume(RightParen, "')' after if condition".to_string(),)?;
+
+		let then_branch = Box::new(self.statement()?,);
+		let else_branch = if self.match_tokens(&[Else],) {
+			Some(Box::new(self.statement()?,),)
+		} else {
+			None
+		};
+		Ok(Stmt::If(cond, then_branch, else_branch,),)
+	}
+
+	fn while_statement(&mut self,) -> ParseResult<Stmt,> {
+		self.consume(LeftParen, "'(' after 'while'".to_string(),)?;
+		let cond = self.expression()?;
+		self.consume(RightParen, "')' after while condition".to_string(),)?;
+		let body = Box::new(self.statement()?,);
+		Ok(Stmt::While(cond, body,),)
+	}
+
+	/// Desugars `for (init; cond; incr) body` into a `while` loop, wrapped in
+	/// a block when there is an initializer, matching the book's approach of reusing existing AST nodes
+	/// rather than adding a dedicated `Stmt::For`.
+	fn for_statement(&mut self,) -> ParseResult<Stmt,> {
+		self.consume(LeftParen, "'(' after 'for'".to_string(),)?;
+
+		let init = if self.match_tokens(&[Semicolon],) {
+			None
+		} else if self.match_tokens(&[Var],) {
+			Some(self.var_declaration()?,)
+		} else {
+			Some(self.expression_statement()?,)
+		};
+
+		let cond = if self.check(Semicolon,) {
+			Expr::Literal(Literal::Bool(true,),)
+		} else {
+			self.expression()?
+		};
+		self.consume(Semicolon, "';' after loop condition".to_string(),)?;
+
+		let incr = if self.check(RightParen,) { None } else { Some(self.expression()?,) };
+		self.consume(RightParen, "')' after for clauses".to_string(),)?;
+
+		let mut body = self.statement()?;
+
+		if let Some(incr,) = incr {
+			body = Stmt::Block(vec![body, Stmt::Expression(incr,)],);
+		}
+		body = Stmt::While(cond, Box::new(body,),);
+		if let Some(init,) = init {
+			body = Stmt::Block(vec![init, body],);
+		}
+
+		Ok(body,)
+	}
+
+	fn block(&mut self,) -> ParseResult<Vec<Stmt,>,> {
+		let mut stmts = vec![];
+		while !self.check(RightBrace,) && !self.at_end() {
+			if let Some(stmt,) = self.declaration() {
+				stmts.push(stmt,);
+			}
+		}
+		self.consume(RightBrace, "'}' after block".to_string(),)?;
+		Ok(stmts,)
+	}
+
+	fn expression_statement(&mut self,) -> ParseResult<Stmt,> {
+		let expr = self.expression()?;
+		self.consume(Semicolon, "';' after expression".to_string(),)?;
+		Ok(Stmt::Expression(expr,),)
+	}
+
+	fn expression(&mut self,) -> ParseResult<Expr,> { self.assignment() }
+
+	fn assignment(&mut self,) -> ParseResult<Expr,> {
+		let expr = self.logic_or()?;
+
+		if self.match_tokens(&[Equal],) {
+			let equals = self.previous().clone();
+			let value = self.assignment()?;
+
+			return if let Expr::Variable(name,) = expr {
+				Ok(Expr::Assign(name, Box::new(value,),),)
+			} else {
+				self.error(&equals, ErrorKind::InvalidAssignmentTarget,);
+				Ok(value,)
+			};
+		}
+
+		Ok(expr,)
+	}
+
+	fn logic_or(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.logic_and()?;
+		while self.match_tokens(&[Or],) {
+			let op = self.previous().clone();
+			let right = self.logic_and()?;
+			expr = Expr::Logical(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn logic_and(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.equality()?;
+		while self.match_tokens(&[And],) {
+			let op = self.previous().clone();
+			let right = self.equality()?;
+			expr = Expr::Logical(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn equality(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.comparison()?;
+		while self.match_tokens(&[BangEqual, EqualEqual],) {
+			let op = self.previous().clone();
+			let right = self.comparison()?;
+			expr = Expr::Binary(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn comparison(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.term()?;
+		while self.match_tokens(&[Greater, GreaterEqual, Less, LessEqual],) {
+			let op = self.previous().clone();
+			let right = self.term()?;
+			expr = Expr::Binary(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn term(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.factor()?;
+		while self.match_tokens(&[Plus, Minus],) {
+			let op = self.previous().clone();
+			let right = self.factor()?;
+			expr = Expr::Binary(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn factor(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.unary()?;
+		while self.match_tokens(&[Star, Slash, Mod],) {
+			let op = self.previous().clone();
+			let right = self.unary()?;
+			expr = Expr::Binary(Box::new(expr,), op, Box::new(right,),);
+		}
+		Ok(expr,)
+	}
+
+	fn unary(&mut self,) -> ParseResult<Expr,> {
+		if self.match_tokens(&[Bang, Minus],) {
+			let op = self.previous().clone();
+			let right = self.unary()?;
+			return Ok(Expr::Unary(op, Box::new(right,),),);
+		}
+		self.call()
+	}
+
+	fn call(&mut self,) -> ParseResult<Expr,> {
+		let mut expr = self.primary()?;
+
+		loop {
+			if self.match_tokens(&[LeftParen],) {
+				expr = self.finish_call(expr,)?;
+			} else {
+				break;
+			}
+		}
+
+		Ok(expr,)
+	}
+
+	fn finish_call(&mut self, callee: Expr,) -> ParseResult<Expr,> {
+		let mut args = vec![];
+		if !self.check(RightParen,) {
+			loop {
+				args.push(self.expression()?,);
+				if !self.match_tokens(&[Comma],) {
+					break;
+				}
+			}
+		}
+		let paren = self.consume(RightParen, "')' after arguments".to_string(),)?;
+		Ok(Expr::Call(Box::new(callee,), paren, args,),)
+	}
+
+	fn primary(&mut self,) -> ParseResult<Expr,> {
+		if self.match_tokens(&[False],) {
+			return Ok(Expr::Literal(Literal::Bool(false,),),);
+		}
+		if self.match_tokens(&[True],) {
+			return Ok(Expr::Literal(Literal::Bool(true,),),);
+		}
+		if self.match_tokens(&[Nil],) {
+			return Ok(Expr::Literal(Literal::Nil,),);
+		}
+		if self.match_tokens(&[Number],) {
+			let n = self.previous().lexeme().parse().unwrap_or(0.0,);
+			return Ok(Expr::Literal(Literal::Number(n,),),);
+		}
+		if self.match_tokens(&[Str],) {
+			let s = self.previous().lexeme();
+			let s = s[1..s.len() - 1].to_string();
+			return Ok(Expr::Literal(Literal::Str(s,),),);
+		}
+		if self.match_tokens(&[Char],) {
+			let lexeme = self.previous().lexeme();
+			let c = lexeme.chars().nth(1,).unwrap_or('\0',);
+			return Ok(Expr::Literal(Literal::Char(c,),),);
+		}
+		if self.match_tokens(&[Identifier],) {
+			return Ok(Expr::Variable(self.previous().clone(),),);
+		}
+		if self.match_tokens(&[LeftParen],) {
+			let expr = self.expression()?;
+			self.consume(RightParen, "')' after expression".to_string(),)?;
+			return Ok(Expr::Grouping(Box::new(expr,),),);
+		}
+
+		let found = self.peek().token_type().clone();
+		let err = ErrorKind::ExpectedToken("expression".to_string(), found,);
+		Err(self.error(self.peek(), err,),)
+	}
+
+	fn match_tokens(&mut self, types: &[TokenType],) -> bool {
+		for tt in types {
+			if self.check(tt.clone(),) {
+				self.advance();
+				return true;
+			}
+		}
+		false
+	}
+
+	fn consume(&mut self, tt: TokenType, what: String,) -> ParseResult<Token,> {
+		if self.check(tt,) {
+			return Ok(self.advance().clone(),);
+		}
+		let found = self.peek().token_type().clone();
+		let err = ErrorKind::ExpectedToken(what, found,);
+		Err(self.error(self.peek(), err,),)
+	}
+
+	fn check(&self, tt: TokenType,) -> bool {
+		!self.at_end() && self.peek().token_type() == &tt
+	}
+
+	fn advance(&mut self,) -> &Token {
+		if !self.at_end() {
+			self.current += 1;
+		}
+		self.previous()
+	}
+
+	fn at_end(&self,) -> bool { self.peek().token_type() == &EOF }
+
+	fn peek(&self,) -> &Token { &self.tokens[self.current] }
+
+	fn previous(&self,) -> &Token { &self.tokens[self.current - 1] }
+
+	fn error(&self, token: &Token, kind: ErrorKind,) -> InterpreterError {
+		let err = InterpreterError::new().occur(kind,).at(token.span(),);
+		err_report::report(&self.src, &err,);
+		self.sink.push(err.clone(),);
+		err
+	}
+
+	/// Panic-mode recovery: discard tokens until we're positioned at a
+	/// likely statement boundary, so a single syntax error doesn't abort
+	/// parsing of the rest of the file.
+	fn synchronize(&mut self,) {
+		self.advance();
+
+		while !self.at_end() {
+			if self.previous().token_type() == &Semicolon {
+				return;
+			}
+
+			if matches!(self.peek().token_type(), Class | Fun | Var | For | If | While | Print | Return) {
+				return;
+			}
+
+			self.advance();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::err_report::ErrorSink;
+	use crate::scanner::Scanner;
+
+	fn parse(src: &str,) -> (Vec<Stmt,>, ErrorSink,) {
+		let src = src.to_string();
+		let tokens = Scanner::new(src.clone(),).scan_tokens();
+		let sink = ErrorSink::new();
+		let stmts = Parser::new(tokens, src, sink.clone(),).parse();
+		(stmts, sink,)
+	}
+
+	#[test]
+	fn binary_precedence_test() {
+		let (stmts, sink,) = parse("1 + 2 * 3;",);
+		assert!(!sink.had_error());
+		match &stmts[0] {
+			Stmt::Expression(Expr::Binary(l, op, r,),) => {
+				assert_eq!(op.token_type(), &Plus);
+				assert_eq!(**l, Expr::Literal(Literal::Number(1.0,),));
+				match r.as_ref() {
+					Expr::Binary(l, op, r,) => {
+						assert_eq!(op.token_type(), &Star);
+						assert_eq!(**l, Expr::Literal(Literal::Number(2.0,),));
+						assert_eq!(**r, Expr::Literal(Literal::Number(3.0,),));
+					},
+					_ => panic!("expected a nested binary expression"),
+				}
+			},
+			_ => panic!("expected an expression statement"),
+		}
+	}
+
+	#[test]
+	fn var_declaration_test() {
+		let (stmts, sink,) = parse("var a = 1;",);
+		assert!(!sink.had_error());
+		match &stmts[0] {
+			Stmt::Var(name, Some(init,),) => {
+				assert_eq!(name.lexeme(), "a");
+				assert_eq!(*init, Expr::Literal(Literal::Number(1.0,),));
+			},
+			_ => panic!("expected a var declaration with an initializer"),
+		}
+	}
+
+	#[test]
+	fn assignment_test() {
+		let (stmts, sink,) = parse("a = 1;",);
+		assert!(!sink.had_error());
+		match &stmts[0] {
+			Stmt::Expression(Expr::Assign(name, value,),) => {
+				assert_eq!(name.lexeme(), "a");
+				assert_eq!(**value, Expr::Literal(Literal::Number(1.0,),));
+			},
+			_ => panic!("expected an assignment expression statement"),
+		}
+	}
+
+	#[test]
+	fn if_else_test() {
+		let (stmts, sink,) = parse("if (a) print 1; else print 2;",);
+		assert!(!sink.had_error());
+		match &stmts[0] {
+			Stmt::If(cond, then_branch, Some(else_branch,),) => {
+				assert!(matches!(cond, Expr::Variable(name,) if name.lexeme() == "a"));
+				assert!(matches!(then_branch.as_ref(), Stmt::Print(_)));
+				assert!(matches!(else_branch.as_ref(), Stmt::Print(_)));
+			},
+			_ => panic!("expected an if/else statement"),
+		}
+	}
+
+	#[test]
+	fn while_test() {
+		let (stmts, sink,) = parse("while (a) print 1;",);
+		assert!(!sink.had_error());
+		match &stmts[0] {
+			Stmt::While(_, body,) => assert!(matches!(body.as_ref(), Stmt::Print(_))),
+			_ => panic!("expected a while statement"),
+		}
+	}
+
+	#[test]
+	fn synchronize_after_error_test() {
+		let (stmts, sink,) = parse("var ; print 1;",);
+		assert!(sink.had_error());
+		assert_eq!(stmts.len(), 1);
+		assert!(matches!(&stmts[0], Stmt::Print(_)));
+	}
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::err_report;
+use crate::err_report::ErrorSink;
 use crate::err_report::InterpreterError;
+use crate::token::Span;
 use crate::token::Token;
 use crate::token::TokenType;
 use crate::token::TokenType::*;
@@ -9,13 +11,16 @@ use crate::token::TokenType::*;
 /// Scanner for the lox language.
 #[derive(Clone, Debug,)]
 pub struct Scanner {
-	had_err:  InterpreterError,
-	src:      String,
-	tokens:   Vec<Token,>,
-	start:    usize,
-	current:  usize,
-	line:     usize,
-	keywords: HashMap<String, TokenType,>,
+	sink:       ErrorSink,
+	src:        String,
+	chars:      Vec<char,>,
+	tokens:     Vec<Token,>,
+	start:      usize,
+	current:    usize,
+	line:       usize,
+	line_start: usize,
+	keywords:   HashMap<String, TokenType,>,
+	done:       bool,
 }
 
 impl Scanner {
@@ -25,28 +30,28 @@ impl Scanner {
 			keywords.insert(tt.keywords().unwrap(), tt,);
 		}
 
+		let chars = src.chars().collect();
 		Self {
-			had_err: InterpreterError::new(),
+			sink: ErrorSink::new(),
 			src,
+			chars,
 			tokens: vec![],
 			start: 0,
 			current: 0,
 			line: 1,
+			line_start: 0,
 			keywords,
+			done: false,
 		}
 	}
 
-	pub fn scan_tokens(mut self,) -> Vec<Token,> {
-		let this = &mut self;
-		while !this.at_end() {
-			this.scan_token();
-			this.start = this.current;
-		}
+	/// The shared sink this scanner reports into, so a parser and
+	/// interpreter created afterwards can accumulate into the same place.
+	pub fn sink(&self,) -> ErrorSink { self.sink.clone() }
 
-		//pushing the EOF token cannot be done by scan_token()
-		self.tokens.push(Token::new(EOF, "".to_string(),),);
-		self.tokens
-	}
+	/// Collects the whole token stream eagerly. A thin convenience over
+	/// pulling from [`Iterator::next`] one token at a time.
+	pub fn scan_tokens(self,) -> Vec<Token,> { self.collect() }
 
 	fn scan_token(&mut self,) {
 		match self.eat() {
@@ -89,17 +94,23 @@ impl Scanner {
 				}
 			},
 			' ' | '\r' | '\t' => (), // ignore whitespace, tab(indent)
-			'\n' => self.line += 1,
+			'\n' => {
+				self.line += 1;
+				self.line_start = self.current;
+			},
 			'"' => self.string(),
+			'\'' => self.char_literal(),
 			c => {
 				if self.is_digit(c,) {
 					self.number();
 				} else if self.is_alpha(c,) {
 					self.identifier()
 				} else {
+					let span = self.current_span();
 					self.error(
 						InterpreterError::new()
-							.occur(err_report::ErrorKind::UnexpectedCharacter(c,),),
+							.occur(err_report::ErrorKind::UnexpectedCharacter(c,),)
+							.at(span,),
 					)
 				}
 			},
@@ -137,9 +148,9 @@ impl Scanner {
 		true
 	}
 
-	fn peek(&self,) -> char { self.src.chars().nth(self.current,).unwrap_or('\0',) }
+	fn peek(&self,) -> char { self.chars.get(self.current,).copied().unwrap_or('\0',) }
 
-	fn peek2(&self,) -> char { self.src.chars().nth(self.current + 1,).unwrap_or('\0',) }
+	fn peek2(&self,) -> char { self.chars.get(self.current + 1,).copied().unwrap_or('\0',) }
 
 	fn is_alphanumeric(&self, c: char,) -> bool { self.is_alpha(c,) || self.is_digit(c,) }
 
@@ -150,23 +161,158 @@ impl Scanner {
 	}
 
 	fn string(&mut self,) {
-		self.current += 1;
+		let mut value = String::new();
 		while !(self.peek() == '"' || self.at_end()) {
-			if self.peek() == '\n' {
-				self.line += 1;
+			match self.eat() {
+				'\n' => {
+					self.line += 1;
+					self.line_start = self.current;
+					value.push('\n',);
+				},
+				'\\' => {
+					if let Some(c,) = self.escape() {
+						value.push(c,);
+					}
+				},
+				c => value.push(c,),
 			}
-			self.eat();
 		}
 
 		if self.at_end() {
-			self.error(InterpreterError::new().occur(err_report::ErrorKind::UnterminatedString(
-				self.src[self.start..].to_string(),
-			),),);
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new()
+					.occur(err_report::ErrorKind::UnterminatedString(
+						self.src[self.start..].to_string(),
+					),)
+					.at(span,),
+			);
 			return;
 		}
 
 		self.current += 1;
-		self.add_token(Str,);
+		let lexeme = format!("\"{value}\"");
+		let span = self.current_span();
+		self.tokens.push(Token::new(Str, lexeme, span,),);
+	}
+
+	/// Scans a single-quoted character literal like `'a'` or `'\n'`.
+	fn char_literal(&mut self,) {
+		if self.at_end() {
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new()
+					.occur(err_report::ErrorKind::UnterminatedChar(
+						self.src[self.start..self.current].to_string(),
+					),)
+					.at(span,),
+			);
+			return;
+		}
+		if self.peek() == '\'' {
+			self.eat();
+			let span = self.current_span();
+			self.error(InterpreterError::new().occur(err_report::ErrorKind::EmptyChar,).at(span,),);
+			return;
+		}
+
+		let value = if self.peek() == '\\' {
+			self.eat();
+			self.escape()
+		} else {
+			Some(self.eat(),)
+		};
+
+		if self.at_end() {
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new()
+					.occur(err_report::ErrorKind::UnterminatedChar(
+						self.src[self.start..self.current].to_string(),
+					),)
+					.at(span,),
+			);
+			return;
+		}
+		if self.peek() != '\'' {
+			// More than one character before the closing quote: keep consuming
+			// up to it (if any) so the rest of the file stays in sync.
+			while self.peek() != '\'' && !self.at_end() {
+				self.eat();
+			}
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new()
+					.occur(err_report::ErrorKind::CharTooLong(self.src[self.start..self.current].to_string(),),)
+					.at(span,),
+			);
+			if !self.at_end() {
+				self.eat();
+			}
+			return;
+		}
+		self.eat();
+
+		let lexeme = match value {
+			Some(c,) => format!("'{c}'"),
+			None => self.src[self.start..self.current].to_string(),
+		};
+		let span = self.current_span();
+		self.tokens.push(Token::new(Char, lexeme, span,),);
+	}
+
+	/// Decodes the escape sequence following a backslash the caller already
+	/// consumed, reporting `ErrorKind::InvalidEscape` for anything unknown.
+	fn escape(&mut self,) -> Option<char,> {
+		if self.at_end() {
+			return None;
+		}
+		match self.eat() {
+			'n' => Some('\n',),
+			't' => Some('\t',),
+			'r' => Some('\r',),
+			'"' => Some('"',),
+			'\'' => Some('\'',),
+			'\\' => Some('\\',),
+			'0' => Some('\0',),
+			'u' => self.unicode_escape(),
+			other => {
+				let span = self.current_span();
+				self.error(
+					InterpreterError::new().occur(err_report::ErrorKind::InvalidEscape(other,),).at(span,),
+				);
+				None
+			},
+		}
+	}
+
+	/// Decodes a `\u{...}` escape, where `{` has not yet been consumed.
+	fn unicode_escape(&mut self,) -> Option<char,> {
+		if self.peek() != '{' {
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new().occur(err_report::ErrorKind::InvalidEscape('u',),).at(span,),
+			);
+			return None;
+		}
+		self.eat();
+
+		let mut hex = String::new();
+		while self.peek() != '}' && !self.at_end() {
+			hex.push(self.eat(),);
+		}
+		if self.at_end() {
+			return None;
+		}
+		self.eat();
+
+		u32::from_str_radix(&hex, 16,).ok().and_then(char::from_u32,).or_else(|| {
+			let span = self.current_span();
+			self.error(
+				InterpreterError::new().occur(err_report::ErrorKind::InvalidEscape('u',),).at(span,),
+			);
+			None
+		},)
 	}
 
 	fn number(&mut self,) {
@@ -199,11 +345,13 @@ impl Scanner {
 		while !(self.next_is('*',) && self.next_is('/',)) {
 			if self.next_is('\n',) {
 				self.line += 1;
+				self.line_start = self.current;
 			} else if self.next_is('/',) && self.next_is('*',) {
 				self.block_comment();
 			} else if self.current >= self.src.len() {
+				let span = self.current_span();
 				self.error(
-					InterpreterError::new().occur(err_report::ErrorKind::UnterminatedComment,),
+					InterpreterError::new().occur(err_report::ErrorKind::UnterminatedComment,).at(span,),
 				);
 			} else {
 				self.eat();
@@ -211,14 +359,50 @@ impl Scanner {
 		}
 	}
 
+	fn current_span(&self,) -> Span {
+		Span {
+			line:   self.line,
+			column: self.start - self.line_start + 1,
+			start:  self.start,
+			end:    self.current,
+		}
+	}
+
 	fn add_token(&mut self, tt: TokenType,) {
 		let lexeme = self.src[self.start..self.current].to_string();
-		self.tokens.push(Token::new(tt, lexeme,),);
+		self.tokens.push(Token::new(tt, lexeme, self.current_span(),),);
 	}
 
 	fn error(&mut self, e: InterpreterError,) {
-		self.had_err = e.clone();
-		err_report::error(self.line, e,)
+		err_report::report(&self.src, &e,);
+		self.sink.push(e,);
+	}
+}
+
+/// Pulls one [`Token`] at a time, running `scan_token` lazily instead of
+/// tokenizing the whole source up front. This lets a single-pass consumer
+/// (like the bytecode [`crate::bytecode::Compiler`]) request tokens on
+/// demand without the scanner ever buffering more than one at a time.
+impl Iterator for Scanner {
+	type Item = Token;
+
+	fn next(&mut self,) -> Option<Token,> {
+		if self.done {
+			return None;
+		}
+
+		while !self.at_end() {
+			let before = self.tokens.len();
+			self.scan_token();
+			self.start = self.current;
+			if self.tokens.len() > before {
+				return self.tokens.pop();
+			}
+		}
+
+		self.done = true;
+		let span = self.current_span();
+		Some(Token::new(EOF, "".to_string(), span,),)
 	}
 }
 
@@ -245,16 +429,20 @@ mod tests {
 		assert_eq!(scanner.scan_tokens().len(), 1,);
 	}
 
+	fn span(line: usize, column: usize, start: usize, end: usize,) -> Span {
+		Span { line, column, start, end, }
+	}
+
 	#[test]
 	fn statements_test() {
 		let scanner = Scanner::new("var a = 1;".to_string(),);
 		let expect = vec![
-			Token::new(Var, "var".to_string(),),
-			Token::new(Identifier, "a".to_string(),),
-			Token::new(Equal, "=".to_string(),),
-			Token::new(Number, "1".to_string(),),
-			Token::new(Semicolon, ";".to_string(),),
-			Token::new(EOF, "".to_string(),),
+			Token::new(Var, "var".to_string(), span(1, 1, 0, 3,),),
+			Token::new(Identifier, "a".to_string(), span(1, 5, 4, 5,),),
+			Token::new(Equal, "=".to_string(), span(1, 7, 6, 7,),),
+			Token::new(Number, "1".to_string(), span(1, 9, 8, 9,),),
+			Token::new(Semicolon, ";".to_string(), span(1, 10, 9, 10,),),
+			Token::new(EOF, "".to_string(), span(1, 11, 10, 10,),),
 		];
 		assert_eq!(expect, scanner.scan_tokens(),);
 	}
@@ -263,20 +451,20 @@ mod tests {
 	fn multiline_test() {
 		let scanner = Scanner::new("var a = 1;\nvar b = 2;\nprint \"hello world\";".to_string(),);
 		let expect = vec![
-			Token::new(Var, "var".to_string(),),
-			Token::new(Identifier, "a".to_string(),),
-			Token::new(Equal, "=".to_string(),),
-			Token::new(Number, "1".to_string(),),
-			Token::new(Semicolon, ";".to_string(),),
-			Token::new(Var, "var".to_string(),),
-			Token::new(Identifier, "b".to_string(),),
-			Token::new(Equal, "=".to_string(),),
-			Token::new(Number, "2".to_string(),),
-			Token::new(Semicolon, ";".to_string(),),
-			Token::new(Print, "print".to_string(),),
-			Token::new(Str, "\"hello world\"".to_string(),),
-			Token::new(Semicolon, ";".to_string(),),
-			Token::new(EOF, "".to_string(),),
+			Token::new(Var, "var".to_string(), span(1, 1, 0, 3,),),
+			Token::new(Identifier, "a".to_string(), span(1, 5, 4, 5,),),
+			Token::new(Equal, "=".to_string(), span(1, 7, 6, 7,),),
+			Token::new(Number, "1".to_string(), span(1, 9, 8, 9,),),
+			Token::new(Semicolon, ";".to_string(), span(1, 10, 9, 10,),),
+			Token::new(Var, "var".to_string(), span(2, 1, 11, 14,),),
+			Token::new(Identifier, "b".to_string(), span(2, 5, 15, 16,),),
+			Token::new(Equal, "=".to_string(), span(2, 7, 17, 18,),),
+			Token::new(Number, "2".to_string(), span(2, 9, 19, 20,),),
+			Token::new(Semicolon, ";".to_string(), span(2, 10, 20, 21,),),
+			Token::new(Print, "print".to_string(), span(3, 1, 22, 27,),),
+			Token::new(Str, "\"hello world\"".to_string(), span(3, 7, 28, 41,),),
+			Token::new(Semicolon, ";".to_string(), span(3, 20, 41, 42,),),
+			Token::new(EOF, "".to_string(), span(3, 21, 42, 42,),),
 		];
 		assert_eq!(expect, scanner.scan_tokens(),);
 	}
@@ -285,13 +473,68 @@ mod tests {
 	fn comment_test() {
 		let scanner = Scanner::new("var a = 1; // this is a comment".to_string(),);
 		let expect = vec![
-			Token::new(Var, "var".to_string(),),
-			Token::new(Identifier, "a".to_string(),),
-			Token::new(Equal, "=".to_string(),),
-			Token::new(Number, "1".to_string(),),
-			Token::new(Semicolon, ";".to_string(),),
-			Token::new(EOF, "".to_string(),),
+			Token::new(Var, "var".to_string(), span(1, 1, 0, 3,),),
+			Token::new(Identifier, "a".to_string(), span(1, 5, 4, 5,),),
+			Token::new(Equal, "=".to_string(), span(1, 7, 6, 7,),),
+			Token::new(Number, "1".to_string(), span(1, 9, 8, 9,),),
+			Token::new(Semicolon, ";".to_string(), span(1, 10, 9, 10,),),
+			Token::new(EOF, "".to_string(), span(1, 32, 31, 31,),),
 		];
 		assert_eq!(expect, scanner.scan_tokens(),);
 	}
+
+	#[test]
+	fn string_escape_test() {
+		let scanner = Scanner::new("var s = \"a\\nb\";".to_string(),);
+		let expect = vec![
+			Token::new(Var, "var".to_string(), span(1, 1, 0, 3,),),
+			Token::new(Identifier, "s".to_string(), span(1, 5, 4, 5,),),
+			Token::new(Equal, "=".to_string(), span(1, 7, 6, 7,),),
+			Token::new(Str, "\"a\nb\"".to_string(), span(1, 9, 8, 14,),),
+			Token::new(Semicolon, ";".to_string(), span(1, 15, 14, 15,),),
+			Token::new(EOF, "".to_string(), span(1, 16, 15, 15,),),
+		];
+		assert_eq!(expect, scanner.scan_tokens(),);
+	}
+
+	#[test]
+	fn char_literal_test() {
+		let scanner = Scanner::new("var c = 'a';".to_string(),);
+		let expect = vec![
+			Token::new(Var, "var".to_string(), span(1, 1, 0, 3,),),
+			Token::new(Identifier, "c".to_string(), span(1, 5, 4, 5,),),
+			Token::new(Equal, "=".to_string(), span(1, 7, 6, 7,),),
+			Token::new(Char, "'a'".to_string(), span(1, 9, 8, 11,),),
+			Token::new(Semicolon, ";".to_string(), span(1, 12, 11, 12,),),
+			Token::new(EOF, "".to_string(), span(1, 13, 12, 12,),),
+		];
+		assert_eq!(expect, scanner.scan_tokens(),);
+	}
+
+	#[test]
+	fn empty_char_literal_test() {
+		let scanner = Scanner::new("'';".to_string(),);
+		let sink = scanner.sink();
+		let tokens = scanner.scan_tokens();
+		assert_eq!(tokens.len(), 2); // Semicolon, EOF
+		assert!(sink.had_error());
+	}
+
+	#[test]
+	fn char_literal_too_long_test() {
+		let scanner = Scanner::new("'ab';".to_string(),);
+		let sink = scanner.sink();
+		let tokens = scanner.scan_tokens();
+		assert_eq!(tokens.len(), 2); // Semicolon, EOF
+		assert!(sink.had_error());
+	}
+
+	#[test]
+	fn invalid_escape_test() {
+		let scanner = Scanner::new("\"\\q\";".to_string(),);
+		let sink = scanner.sink();
+		let tokens = scanner.scan_tokens();
+		assert_eq!(tokens.len(), 3); // Str, Semicolon, EOF
+		assert!(sink.had_error());
+	}
 }
@@ -31,6 +31,7 @@ pub enum TokenType {
 	Identifier,
 	Str,
 	Number,
+	Char,
 
 	// keywords
 	And,
@@ -81,6 +82,7 @@ impl Display for TokenType {
 			Identifier => "token_identifier",
 			Str => "token_string",
 			Number => "token_number",
+			Char => "token_char",
 			And => "and",
 			Assert => "assert",
 			Class => "class",
@@ -165,24 +167,25 @@ impl TokenType {
 			Identifier => 20,
 			Str => 21,
 			Number => 22,
-			And => 23,
-			Assert => 24,
-			Class => 25,
-			Else => 26,
-			False => 27,
-			Fun => 28,
-			For => 29,
-			If => 30,
-			Nil => 31,
-			Or => 32,
-			Print => 33,
-			Return => 34,
-			Super => 35,
-			This => 36,
-			True => 37,
-			Var => 38,
-			While => 39,
-			EOF => 40,
+			Char => 23,
+			And => 24,
+			Assert => 25,
+			Class => 26,
+			Else => 27,
+			False => 28,
+			Fun => 29,
+			For => 30,
+			If => 31,
+			Nil => 32,
+			Or => 33,
+			Print => 34,
+			Return => 35,
+			Super => 36,
+			This => 37,
+			True => 38,
+			Var => 39,
+			While => 40,
+			EOF => 41,
 		}
 	}
 
@@ -212,38 +215,60 @@ impl TokenType {
 			20 => Some(Identifier,),
 			21 => Some(Str,),
 			22 => Some(Number,),
-			23 => Some(And,),
-			24 => Some(Assert,),
-			25 => Some(Class,),
-			26 => Some(Else,),
-			27 => Some(False,),
-			28 => Some(Fun,),
-			29 => Some(For,),
-			30 => Some(If,),
-			31 => Some(Nil,),
-			32 => Some(Or,),
-			33 => Some(Print,),
-			34 => Some(Return,),
-			35 => Some(Super,),
-			36 => Some(This,),
-			37 => Some(True,),
-			38 => Some(Var,),
-			39 => Some(While,),
-			40 => Some(EOF,),
+			23 => Some(Char,),
+			24 => Some(And,),
+			25 => Some(Assert,),
+			26 => Some(Class,),
+			27 => Some(Else,),
+			28 => Some(False,),
+			29 => Some(Fun,),
+			30 => Some(For,),
+			31 => Some(If,),
+			32 => Some(Nil,),
+			33 => Some(Or,),
+			34 => Some(Print,),
+			35 => Some(Return,),
+			36 => Some(Super,),
+			37 => Some(This,),
+			38 => Some(True,),
+			39 => Some(Var,),
+			40 => Some(While,),
+			41 => Some(EOF,),
 			_ => None,
 		}
 	}
 }
 
+/// A token's position in the source: line/column for diagnostics plus the
+/// start/end offsets (counted in characters, not bytes), so errors can
+/// underline the exact offending text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+pub struct Span {
+	pub line:   usize,
+	pub column: usize,
+	pub start:  usize,
+	pub end:    usize,
+}
+
 #[derive(Debug, Clone, PartialEq,)]
 pub struct Token {
 	token_type: TokenType,
 	lexeme:     String,
-	//line:       usize,
+	span:       Span,
 }
 
 impl Token {
-	pub fn new(token_type: TokenType, lexeme: String,) -> Self { Self { token_type, lexeme, } }
+	pub fn new(token_type: TokenType, lexeme: String, span: Span,) -> Self {
+		Self { token_type, lexeme, span, }
+	}
+
+	pub(crate) fn token_type(&self,) -> &TokenType { &self.token_type }
+
+	pub(crate) fn lexeme(&self,) -> &str { &self.lexeme }
+
+	pub(crate) fn line(&self,) -> usize { self.span.line }
+
+	pub(crate) fn span(&self,) -> Span { self.span }
 }
 
 impl Display for Token {